@@ -55,6 +55,101 @@ tiny_logger::set_verbosity(5);
 // Messages logged at any nonnegative level will now be emitted from here on.
 ```
 
+## Per-Channel Verbosity
+
+In addition to the global verbosity level, each channel can be given its own threshold with
+`Channel::set_verbosity`, which takes priority over the global level for that channel only:
+
+```rust
+use tiny_logger::Channel;
+
+tiny_logger::set_verbosity(0);       // Global default.
+Channel::Trace.set_verbosity(2);     // Trace is noisier than the default.
+Channel::Debug.set_verbosity(tiny_logger::LogLevel::MIN); // Suppress Debug entirely.
+```
+
+You can also set verbosity from an env-style directive string with `set_from_env`, which reads an environment
+variable such as `MY_APP_LOG=info,trace=2,debug=off` and applies it: a bare token like `info` sets the global
+default, `channel=N` sets a numeric threshold for that channel, and `channel=off` suppresses the channel.
+Unknown or malformed tokens are ignored rather than causing a panic.
+
+```rust
+tiny_logger::set_from_env("MY_APP_LOG");
+```
+
+## Timestamps
+
+By default, log lines carry no timestamp. You can opt in with `set_timestamp`, choosing UTC or local time, and
+customize the rendering with a `strftime`-style pattern via `set_timestamp_format`:
+
+```rust
+use tiny_logger::TimestampMode;
+
+tiny_logger::set_timestamp(TimestampMode::Utc);
+tiny_logger::set_timestamp_format("%Y-%m-%dT%H:%M:%S");
+```
+
+This is especially useful when logs are written to a file, where wall-clock ordering otherwise has to be
+reconstructed from context.
+
+## Source Location
+
+For channels you enable with `show_source_location`, the `log!` macro (and its per-channel shorthands `critical!`,
+`error!`, `warning!`, `notice!`, `info!`, `debug!`, `trace!`) append the call site to the emitted message, which is
+invaluable when tracing control flow through `Debug`/`Trace` output:
+
+```rust
+use tiny_logger::Channel;
+
+tiny_logger::show_source_location(&[Channel::Debug, Channel::Trace]);
+tiny_logger::set_verbosity(1);
+
+tiny_logger::debug!(0, "Variable values are correct."); // "Debug: Variable values are correct. (src/main.rs:8)"
+```
+
+The plain `log()` function never captures the call site; use the macros (or `log_located` directly) when you want
+this behavior.
+
+## Async Logging
+
+By default every call to `log()` locks the destination stream and flushes synchronously, which can stall hot paths.
+Calling `enable_async` moves the actual write onto a background thread: `log()` still formats the line (color,
+timestamp, and all) on the caller's thread, but then hands it off over a bounded channel instead of writing it
+directly.
+
+```rust
+use tiny_logger::AsyncPolicy;
+
+// Queue up to 1024 records; if the queue is full, drop the record rather than block.
+tiny_logger::enable_async(1024, AsyncPolicy::Drop);
+// ...
+tiny_logger::flush(); // Wait for every queued record to be written.
+tiny_logger::disable_async(); // Shut the worker down before exiting.
+```
+
+Use `AsyncPolicy::Block` instead if you'd rather apply backpressure than lose records, and check `dropped_count()` to
+monitor how many records `AsyncPolicy::Drop` has discarded. A record whose write itself fails (a broken pipe, a full
+disk, ...) is isolated to that one record rather than ending the worker thread; check `worker_panic_count()` if you
+need to notice that. Call `disable_async()` before your program exits so buffered records are written; the crate
+can't hook process exit itself since its globals are plain `lazy_static`s.
+
+## Custom Formatting
+
+The default `"{Channel}: {message}"` layout can be replaced entirely with `set_formatter`, which takes a closure
+receiving a `FormatRecord` (channel, color, level, message, and optional timestamp/source location) and returning
+the line to emit:
+
+```rust
+use tiny_logger::{set_formatter, json_line};
+
+// Emit structured JSON lines instead of the default colored layout.
+set_formatter(Box::new(json_line));
+```
+
+A few ready-made formatters are included: `plain` (no color/timestamp/location), `with_level_number` (prefixes the
+numeric verbosity level), and `json_line` (one JSON object per line). Call `clear_formatter()` to go back to the
+built-in layout.
+
 ## Colors
 
 The colors for each channel are global and are given reasonable defaults. To change these defaults, you can call
@@ -120,13 +215,52 @@ assert!(logged_string.contains("This is an Info message.")); // Success!
 ```
 
 When logging to something other than a console, such as a file or a string buffer, you will probably want to disable
-colored/styled output globally with `tiny_logger::disable_color()`.
+colored/styled output globally with `tiny_logger::disable_color()`. Alternatively, call `tiny_logger::auto_color()`
+once at startup to detect non-terminal output (a pipe, a redirect, or `TERM=dumb`) and suppress styling
+automatically, the same way `env_logger` does:
+
+```rust
+tiny_logger::auto_color(); // No-op on a real terminal; suppresses color otherwise.
+```
+
+For channels pointed at a non-terminal stream you configure yourself, such as a file, use
+`Channel::set_stream_with_tty` instead of `Channel::set_stream` to mark it as such up front:
+
+```rust
+use std::sync::{Arc, Mutex};
+use tiny_logger::Channel;
+
+let file: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+Channel::Info.set_stream_with_tty(Arc::clone(&(file as Arc<Mutex<dyn std::io::Write + Send>>)), false);
+```
+
+## Compatibility with the `log` Crate
+
+If you enable the `log-compat` feature, `tiny_logger` can act as a backend for the [`log`](https://docs.rs/log)
+facade, so crates that already log via `log::error!`/`log::warn!`/etc. can route through `tiny_logger` without
+changing a single call site.
+
+```rust,ignore
+fn main() {
+    tiny_logger::init();
+    tiny_logger::set_verbosity(2);
+
+    log::info!("Info: Processing started.");   // Routed through Channel::Info
+    log::error!("This is an error message.");   // Routed through Channel::Error
+}
+```
+
+`log::Level` is mapped onto `Channel` as follows: `Error`→`Error`, `Warn`→`Warning`, `Info`→`Info`, `Debug`→`Debug`,
+and `Trace`→`Trace`. `log::Log::enabled` consults that mapped channel's own verbosity (falling back to the global
+`VERBOSITY`, exactly like `log()`), and `set_verbosity`/`Channel::set_verbosity` automatically refresh the `log`
+crate's static max-level gate, so raising a single channel's verbosity after `init`/`try_init` is enough to let
+its messages through — there's nothing else to re-call.
 
 */
 
 use std::{
   collections::HashMap,
-  io::Write,
+  io::{Write, IsTerminal},
   sync::{RwLock, Mutex}
 };
 use std::sync::Arc;
@@ -152,6 +286,25 @@ lazy_static::lazy_static! {
     RwLock::new(m)
   };
 
+  static ref CHANNEL_VERBOSITY: RwLock<HashMap<Channel, LogLevel>> = RwLock::new(HashMap::new());
+
+  static ref TIMESTAMP_MODE: RwLock<TimestampMode> = RwLock::new(TimestampMode::Off);
+
+  static ref TIMESTAMP_FORMAT: RwLock<String> = RwLock::new("%Y-%m-%d %H:%M:%S%.3f".to_string());
+
+  static ref SOURCE_LOCATION_CHANNELS: RwLock<std::collections::HashSet<Channel>> =
+    RwLock::new(std::collections::HashSet::new());
+
+  static ref ASYNC_SENDER: RwLock<Option<std::sync::mpsc::SyncSender<AsyncMessage>>> = RwLock::new(None);
+
+  static ref ASYNC_POLICY: RwLock<AsyncPolicy> = RwLock::new(AsyncPolicy::Drop);
+
+  static ref ASYNC_WORKER: Mutex<Option<std::thread::JoinHandle<()>>> = Mutex::new(None);
+
+  static ref CHANNEL_TTY: RwLock<HashMap<Channel, bool>> = RwLock::new(HashMap::new());
+
+  static ref FORMATTER: RwLock<Option<Formatter>> = RwLock::new(None);
+
   static ref CHANNEL_COLORS: RwLock<HashMap<Channel, Color>> = {
     let mut m = HashMap::new();
     m.insert(Channel::Critical, Color::Red);
@@ -165,6 +318,14 @@ lazy_static::lazy_static! {
   };
 }
 
+/// Count of log records dropped by the async backend under `AsyncPolicy::Drop` because the
+/// channel was full. See `enable_async` and `dropped_count`.
+static DROPPED_COUNT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// Count of records whose write panicked (e.g. a broken pipe or a full disk) on the async
+/// worker thread. See `enable_async` and `worker_panic_count`.
+static WORKER_PANIC_COUNT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
 /// Channels to which a log entry can be published.
 #[derive(Eq, PartialEq, Clone, Copy, Hash, Debug)]
 pub enum Channel {
@@ -190,32 +351,196 @@ impl Channel {
     colors.insert(*self, color);
   }
 
-  /// Get a string of the name of this channel formatted in color.
+  /// Get a string of the name of this channel formatted in color. If this channel is not a
+  /// terminal (see `is_terminal`), the name is returned unstyled regardless of the global color
+  /// setting.
   pub fn painted_name(&self) -> Painted<String> {
     let channel_name: String = format!("{:?}", self); // Get the name of the channel
-    let color: Color         = self.get_color(); // Get the associated color
     let mut name: Painted<String> = Paint::new(channel_name);
-    name.style = color.into(); // Paint the channel name with the color
+    if self.is_terminal() {
+      let color: Color = self.get_color(); // Get the associated color
+      name.style = color.into(); // Paint the channel name with the color
+    }
     name
   }
 
-  /// Set a new logging stream for this channel.
+  /// Set a new logging stream for this channel. This leaves the channel's terminal hint (see
+  /// `is_terminal`) unchanged, which is true unless `set_stream_with_tty` or `auto_color` set it
+  /// otherwise; use `set_stream_with_tty` to set it along with the stream.
   pub fn set_stream(&self, stream: Arc<Mutex<dyn Write + Send>>) {
     let mut streams = LOGGING_STREAMS.write().unwrap();
     streams.insert(*self, stream); // Update the stream for the channel
   }
+
+  /// Set a new logging stream for this channel along with whether it should be treated as a
+  /// terminal. When `is_tty` is false, `painted_name` never applies color for this channel,
+  /// regardless of the global color setting.
+  pub fn set_stream_with_tty(&self, stream: Arc<Mutex<dyn Write + Send>>, is_tty: bool) {
+    self.set_stream(stream);
+    let mut hints = CHANNEL_TTY.write().unwrap();
+    hints.insert(*self, is_tty);
+  }
+
+  /// Returns whether this channel is currently treated as a terminal for coloring purposes.
+  /// Defaults to true, matching the initial `StdOut` streams.
+  pub fn is_terminal(&self) -> bool {
+    let hints = CHANNEL_TTY.read().unwrap();
+    *hints.get(self).unwrap_or(&true)
+  }
+
+  /// Set this channel's own verbosity threshold, overriding the global verbosity for this
+  /// channel only. Pass `LogLevel::MIN` to suppress the channel entirely.
+  pub fn set_verbosity(&self, level: LogLevel) {
+    let mut verbosity = CHANNEL_VERBOSITY.write().unwrap();
+    verbosity.insert(*self, level);
+    drop(verbosity);
+
+    #[cfg(feature = "log-compat")]
+    TinyLogger::refresh_max_level();
+  }
+
+  /// Get this channel's verbosity threshold, falling back to the global verbosity if this
+  /// channel has no threshold of its own.
+  pub fn get_verbosity(&self) -> LogLevel {
+    let verbosity = CHANNEL_VERBOSITY.read().unwrap();
+    match verbosity.get(self) {
+      Some(level) => *level,
+      None        => get_verbosity(),
+    }
+  }
+
+  /// Match a channel name case-insensitively, e.g. for parsing env-style directives.
+  fn from_name(name: &str) -> Option<Channel> {
+    match name.to_ascii_lowercase().as_str() {
+      "critical" => Some(Channel::Critical),
+      "error"    => Some(Channel::Error),
+      "warning"  => Some(Channel::Warning),
+      "notice"   => Some(Channel::Notice),
+      "info"     => Some(Channel::Info),
+      "debug"    => Some(Channel::Debug),
+      "trace"    => Some(Channel::Trace),
+      _          => None,
+    }
+  }
+
+  /// This channel's position in the `Critical < Error < Warning < Notice < Info < Debug < Trace`
+  /// ordering, used as its default verbosity threshold when a bare level name is given to
+  /// `set_from_env`.
+  fn ordinal(&self) -> LogLevel {
+    match self {
+      Channel::Critical => 0,
+      Channel::Error    => 1,
+      Channel::Warning  => 2,
+      Channel::Notice   => 3,
+      Channel::Info     => 4,
+      Channel::Debug    => 5,
+      Channel::Trace    => 6,
+    }
+  }
 }
 
 /// Set the global verbosity level.
 pub fn set_verbosity(new_value: LogLevel) {
   let mut verbosity = VERBOSITY.write().unwrap();
   *verbosity = new_value;
+  drop(verbosity);
+
+  #[cfg(feature = "log-compat")]
+  TinyLogger::refresh_max_level();
 }
 
 pub fn get_verbosity() -> LogLevel {
   *VERBOSITY.read().unwrap()
 }
 
+/// Parse an env-style directive string such as `"info,trace=2,debug=off"` and apply the
+/// verbosity settings it describes. Each comma-separated token is either a bare level name
+/// (sets the global verbosity), `channel=N` (sets that channel's verbosity to `N`), or
+/// `channel=off` (suppresses that channel entirely). Channel names are matched
+/// case-insensitively. Unknown or malformed tokens are silently ignored so a bad directive
+/// string never panics.
+///
+/// This is typically used to read the directive from an environment variable, e.g.
+/// `tiny_logger::set_from_env("MY_APP_LOG")`.
+pub fn set_from_env(var_name: &str) {
+  let directive = match std::env::var(var_name) {
+    Ok(value) => value,
+    Err(_)    => return,
+  };
+
+  for token in directive.split(',') {
+    let token = token.trim();
+    if token.is_empty() {
+      continue;
+    }
+
+    match token.split_once('=') {
+      Some((channel_name, level_str)) => {
+        if let Some(channel) = Channel::from_name(channel_name.trim()) {
+          let level_str = level_str.trim();
+          if level_str.eq_ignore_ascii_case("off") {
+            channel.set_verbosity(LogLevel::MIN);
+          } else if let Ok(level) = level_str.parse::<LogLevel>() {
+            channel.set_verbosity(level);
+          }
+          // Unparseable levels are ignored.
+        }
+        // Unknown channel names are ignored.
+      },
+      None => {
+        // A bare token sets the global default, either as a channel/level name (using its
+        // position in the Critical..Trace ordering) or as a plain integer.
+        if let Some(channel) = Channel::from_name(token) {
+          set_verbosity(channel.ordinal());
+        } else if let Ok(level) = token.parse::<LogLevel>() {
+          set_verbosity(level);
+        }
+      },
+    }
+  }
+}
+
+/// Controls whether (and in what time zone) `log()` prepends a timestamp to each line.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum TimestampMode {
+  /// No timestamp is emitted. This is the default.
+  Off,
+  /// Timestamps are emitted in UTC.
+  Utc,
+  /// Timestamps are emitted in the local time zone.
+  Local,
+}
+
+/// Set whether (and in what time zone) timestamps are prepended to log lines. Defaults to
+/// `TimestampMode::Off`.
+pub fn set_timestamp(mode: TimestampMode) {
+  let mut timestamp_mode = TIMESTAMP_MODE.write().unwrap();
+  *timestamp_mode = mode;
+}
+
+/// Get the current `TimestampMode`.
+pub fn get_timestamp() -> TimestampMode {
+  *TIMESTAMP_MODE.read().unwrap()
+}
+
+/// Set the `strftime`-style format string used to render timestamps. Defaults to
+/// `"%Y-%m-%d %H:%M:%S%.3f"`.
+pub fn set_timestamp_format(format: &str) {
+  let mut timestamp_format = TIMESTAMP_FORMAT.write().unwrap();
+  *timestamp_format = format.to_string();
+}
+
+/// Render the current time according to the configured `TimestampMode` and format, or `None`
+/// if timestamps are off.
+fn render_timestamp() -> Option<String> {
+  let format = TIMESTAMP_FORMAT.read().unwrap();
+  match get_timestamp() {
+    TimestampMode::Off   => None,
+    TimestampMode::Utc   => Some(chrono::Utc::now().format(&format).to_string()),
+    TimestampMode::Local => Some(chrono::Local::now().format(&format).to_string()),
+  }
+}
+
 /// Unconditionally disable color/styling globally. Use this when logging to a file.
 pub fn disable_color() {
   yansi::disable();
@@ -231,32 +556,490 @@ pub fn color_is_enabled() -> bool {
   yansi::is_enabled()
 }
 
+/// All `Channel` variants, in declaration order. Used internally to iterate every channel.
+const ALL_CHANNELS: [Channel; 7] = [
+  Channel::Critical,
+  Channel::Error,
+  Channel::Warning,
+  Channel::Notice,
+  Channel::Info,
+  Channel::Debug,
+  Channel::Trace,
+];
+
+/// Auto-detect whether *process standard output* is going to a real terminal and suppress colors
+/// accordingly, removing the common footgun of forgetting to call `disable_color()` when
+/// redirecting output to a file. Standard output is considered a non-terminal when it is
+/// piped/redirected or when `TERM=dumb` is set.
+///
+/// This only ever probes process stdout, never the stream a channel is actually configured to
+/// write to: because `Channel::set_stream` erases the concrete stream type, there is no way to
+/// ask an arbitrary `Arc<Mutex<dyn Write + Send>>` whether it happens to be a terminal. Every
+/// channel still using the default "is a terminal" hint is judged by stdout's status, even if
+/// you've routed it elsewhere (e.g. to stderr) with `set_stream`. If a channel's real destination
+/// can disagree with stdout (the common `prog > out.log` pattern, where stderr is still a
+/// terminal), call `set_stream_with_tty` for that channel instead of relying on `auto_color`.
+/// Channels you've already marked explicitly via `set_stream_with_tty` are left alone either way.
+pub fn auto_color() {
+  let term_is_dumb = std::env::var("TERM").map(|term| term == "dumb").unwrap_or(false);
+  let stdout_is_tty = std::io::stdout().is_terminal() && !term_is_dumb;
+
+  let mut hints = CHANNEL_TTY.write().unwrap();
+  for channel in ALL_CHANNELS {
+    if !hints.contains_key(&channel) {
+      hints.insert(channel, stdout_is_tty);
+    }
+  }
+}
+
 /// Log a `message` to the given `Channel` at the specified (verbosity) `LogLevel`.
-/// Only emits a message if the global verbosity level is at least `level`.
+/// Only emits a message if `channel`'s own verbosity (or, absent a per-channel override, the
+/// global verbosity) is at least `level`.
 pub fn log(channel: Channel, log_level: LogLevel, message: &str) {
-  let msg = format!("{}: {}", channel.painted_name(), message);
-
-  if *VERBOSITY.read().unwrap() >= log_level {
-    // Fetch the appropriate logging stream for the channel
-    let channel_streams = LOGGING_STREAMS.read().unwrap();
-    if let Some(log_stream) = channel_streams.get(&channel) {
-      let mut locked_stream = log_stream.lock().unwrap(); // Lock the stream
-      let _ = locked_stream.write(msg.as_bytes());
-      let _ = locked_stream.write(b"\n");
-      locked_stream.flush().unwrap();
+  emit(channel, log_level, message, None);
+}
+
+/// Shared by `log()` and `log_located()`: checks verbosity, builds the line (via the installed
+/// formatter if any, otherwise the default layout), and dispatches it to the sync or async
+/// write path.
+fn emit(channel: Channel, log_level: LogLevel, message: &str, location: Option<(&'static str, u32)>) {
+  if channel.get_verbosity() < log_level {
+    return;
+  }
+
+  let timestamp = render_timestamp();
+  let location = location.filter(|_| channel_shows_location(channel));
+
+  let msg = match FORMATTER.read().unwrap().as_ref() {
+    Some(formatter) => {
+      let record = FormatRecord { channel, color: channel.get_color(), level: log_level, message, timestamp, location };
+      formatter(&record)
+    },
+    None => {
+      let mut line = match &timestamp {
+        Some(ts) => format!("{} {}: {}", ts, channel.painted_name(), message),
+        None     => format!("{}: {}", channel.painted_name(), message),
+      };
+      if let Some((file, source_line)) = location {
+        line.push_str(&format!(" ({}:{})", file, source_line));
+      }
+      line
+    },
+  };
+
+  match ASYNC_SENDER.read().unwrap().as_ref() {
+    Some(sender) => dispatch_async(sender, channel, msg),
+    None         => write_formatted(channel, &msg),
+  }
+}
+
+/// Write an already-formatted line to `channel`'s stream. Used by the synchronous path in
+/// `log()` and by the async worker thread spawned by `enable_async`.
+fn write_formatted(channel: Channel, msg: &str) {
+  // Fetch the appropriate logging stream for the channel
+  let channel_streams = LOGGING_STREAMS.read().unwrap();
+  if let Some(log_stream) = channel_streams.get(&channel) {
+    // A write that panicked mid-record (see `enable_async`) poisons this Mutex; recover it
+    // rather than let every later write to the same channel panic too.
+    let mut locked_stream = log_stream.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let _ = locked_stream.write(msg.as_bytes());
+    let _ = locked_stream.write(b"\n");
+    locked_stream.flush().unwrap();
+  }
+  // Note: If there is no stream for the given channel, we just don't emit the message.
+}
+
+/// A message sent from a logging call to the async worker thread.
+enum AsyncMessage {
+  /// An already-formatted `(channel, line)` record to write.
+  Record(Channel, String),
+  /// A flush barrier; the worker acknowledges through the enclosed sender once every
+  /// previously queued record has been written.
+  Flush(std::sync::mpsc::Sender<()>),
+}
+
+/// Chooses what happens to a log record when the async channel is full.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum AsyncPolicy {
+  /// Drop the record and increment the counter returned by `dropped_count`.
+  Drop,
+  /// Block the calling thread until the worker makes room.
+  Block,
+}
+
+/// Send a formatted record to the async worker, honoring the configured `AsyncPolicy`.
+fn dispatch_async(sender: &std::sync::mpsc::SyncSender<AsyncMessage>, channel: Channel, msg: String) {
+  match *ASYNC_POLICY.read().unwrap() {
+    AsyncPolicy::Block => {
+      let _ = sender.send(AsyncMessage::Record(channel, msg));
+    },
+    AsyncPolicy::Drop => {
+      if sender.try_send(AsyncMessage::Record(channel, msg)).is_err() {
+        DROPPED_COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+      }
+    },
+  }
+}
+
+/// Enable non-blocking async logging: `log()` formats each record (including color and
+/// timestamp) on the caller's thread, then hands it to a bounded queue of the given `capacity`
+/// that a single background thread drains, so hot paths no longer lock the output stream
+/// directly. `policy` controls what happens when the queue is full.
+///
+/// Calling this again replaces the previous async worker; call `flush()` first if you need the
+/// old worker's queue drained.
+///
+/// A write failure (e.g. a broken pipe) is isolated to the record that triggered it instead of
+/// ending the worker thread; see `worker_panic_count`.
+pub fn enable_async(capacity: usize, policy: AsyncPolicy) {
+  *ASYNC_POLICY.write().unwrap() = policy;
+
+  let (sender, receiver) = std::sync::mpsc::sync_channel::<AsyncMessage>(capacity);
+  let handle = std::thread::spawn(move || {
+    for message in receiver {
+      match message {
+        // Isolate a single record's write failure (broken pipe, full disk, ...) so it can't
+        // unwind the worker thread and silently strand every record queued after it.
+        AsyncMessage::Record(channel, msg) => {
+          if std::panic::catch_unwind(|| write_formatted(channel, &msg)).is_err() {
+            WORKER_PANIC_COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+          }
+        },
+        AsyncMessage::Flush(ack) => { let _ = ack.send(()); },
+      }
+    }
+  });
+
+  *ASYNC_SENDER.write().unwrap() = Some(sender);
+  *ASYNC_WORKER.lock().unwrap() = Some(handle);
+}
+
+/// Returns true if `enable_async` has been called and not yet shut down.
+pub fn is_async_enabled() -> bool {
+  ASYNC_SENDER.read().unwrap().is_some()
+}
+
+/// The number of records dropped by the async backend under `AsyncPolicy::Drop` because the
+/// queue was full.
+pub fn dropped_count() -> u64 {
+  DROPPED_COUNT.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// The number of records whose write panicked on the async worker thread (e.g. a broken pipe
+/// or a full disk). The worker recovers and keeps draining the queue, but a nonzero count means
+/// those specific records were lost; check this periodically if that matters to your app.
+pub fn worker_panic_count() -> u64 {
+  WORKER_PANIC_COUNT.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Block until every record queued so far by the async backend has been written. A no-op if
+/// async logging is not enabled.
+pub fn flush() {
+  let sender = ASYNC_SENDER.read().unwrap();
+  if let Some(sender) = sender.as_ref() {
+    let (ack_tx, ack_rx) = std::sync::mpsc::channel();
+    if sender.send(AsyncMessage::Flush(ack_tx)).is_ok() {
+      let _ = ack_rx.recv();
+    }
+  }
+}
+
+/// Disable the async backend, flushing any buffered records and joining the worker thread
+/// first. After this returns, `log()` writes synchronously again. Call this before your
+/// program exits if async logging was enabled, since `lazy_static` globals are never dropped
+/// at process exit and the worker thread would otherwise be killed mid-write.
+pub fn disable_async() {
+  flush();
+
+  let sender = ASYNC_SENDER.write().unwrap().take();
+  drop(sender); // Dropping the sender closes the channel, ending the worker's `for` loop.
+
+  if let Some(handle) = ASYNC_WORKER.lock().unwrap().take() {
+    let _ = handle.join();
+  }
+}
+
+/// The pieces of a log record made available to a formatter installed with `set_formatter`.
+pub struct FormatRecord<'a> {
+  /// The channel the message was logged to.
+  pub channel: Channel,
+  /// The channel's current color.
+  pub color: Color,
+  /// The verbosity level the message was logged at.
+  pub level: LogLevel,
+  /// The message text.
+  pub message: &'a str,
+  /// The rendered timestamp, if `set_timestamp` has enabled one.
+  pub timestamp: Option<String>,
+  /// The `(file, line)` call site, if source location is enabled for this channel.
+  pub location: Option<(&'static str, u32)>,
+}
+
+/// A custom line formatter, as installed by `set_formatter`.
+pub type Formatter = Box<dyn Fn(&FormatRecord) -> String + Send + Sync>;
+
+/// Install a custom line formatter, replacing the built-in `"{Channel}: {message}"` layout.
+/// `log()`/`log_located()` call `f` to build the emitted line instead of using the default
+/// format. See `plain`, `with_level_number`, and `json_line` for ready-made formatters.
+pub fn set_formatter(f: Formatter) {
+  *FORMATTER.write().unwrap() = Some(f);
+}
+
+/// Remove a previously installed formatter, reverting to the default `"{Channel}: {message}"`
+/// layout.
+pub fn clear_formatter() {
+  *FORMATTER.write().unwrap() = None;
+}
+
+/// A ready-made formatter that renders `"{Channel}: {message}"` with no color, timestamp, or
+/// source location, regardless of what's configured globally.
+pub fn plain(record: &FormatRecord) -> String {
+  format!("{:?}: {}", record.channel, record.message)
+}
+
+/// A ready-made formatter like `plain`, but prefixed with the numeric verbosity level, e.g.
+/// `"[2] Info: message"`.
+pub fn with_level_number(record: &FormatRecord) -> String {
+  format!("[{}] {:?}: {}", record.level, record.channel, record.message)
+}
+
+/// A ready-made formatter that renders each record as a single line of JSON, e.g.
+/// `{"channel":"Info","level":1,"msg":"..."}`, so structured-log consumers can ingest output
+/// without regex scraping.
+pub fn json_line(record: &FormatRecord) -> String {
+  format!(
+    "{{\"channel\":\"{:?}\",\"level\":{},\"msg\":\"{}\"}}",
+    record.channel,
+    record.level,
+    json_escape(record.message)
+  )
+}
+
+/// Escape a string for embedding in a JSON string literal.
+fn json_escape(s: &str) -> String {
+  let mut escaped = String::with_capacity(s.len());
+  for c in s.chars() {
+    match c {
+      '"'  => escaped.push_str("\\\""),
+      '\\' => escaped.push_str("\\\\"),
+      '\n' => escaped.push_str("\\n"),
+      '\r' => escaped.push_str("\\r"),
+      '\t' => escaped.push_str("\\t"),
+      '\u{8}' => escaped.push_str("\\b"),
+      '\u{c}' => escaped.push_str("\\f"),
+      // Every other control character must be escaped too, or the result isn't valid JSON.
+      c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+      c    => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+/// Choose which channels should have their call site (`file:line`) appended to emitted messages.
+/// This replaces any previously configured set. Pass an empty slice to turn source location off
+/// for every channel.
+///
+/// ```rust
+/// use tiny_logger::Channel;
+///
+/// // Only Debug and Trace messages carry a file:line suffix.
+/// tiny_logger::show_source_location(&[Channel::Debug, Channel::Trace]);
+/// ```
+pub fn show_source_location(channels: &[Channel]) {
+  let mut enabled = SOURCE_LOCATION_CHANNELS.write().unwrap();
+  *enabled = channels.iter().copied().collect();
+}
+
+/// Returns true if `channel` is currently configured to show its call site.
+fn channel_shows_location(channel: Channel) -> bool {
+  SOURCE_LOCATION_CHANNELS.read().unwrap().contains(&channel)
+}
+
+/// Log a `message` to `channel` at `level`, additionally recording the call site (`file` and
+/// `line`). If `channel` has been enabled via `show_source_location`, the default layout appends a
+/// `(file:line)` suffix, and an installed formatter receives the call site as
+/// `FormatRecord::location`; otherwise this behaves exactly like `log()`. This is normally called
+/// through the [`log!`] macro and its per-channel shorthands rather than directly, since those
+/// macros supply `file!()`/`line!()` for you.
+pub fn log_located(channel: Channel, log_level: LogLevel, file: &'static str, line: u32, message: &str) {
+  emit(channel, log_level, message, Some((file, line)));
+}
+
+/// Log a message to `channel` at `level`, capturing the call site so that `Debug`/`Trace` output
+/// (when enabled via [`show_source_location`]) carries a `file:line` suffix.
+///
+/// ```rust
+/// use tiny_logger::{Channel, log};
+///
+/// log!(Channel::Debug, 0, "Variable values are correct.");
+/// ```
+#[macro_export]
+macro_rules! log {
+  ($channel:expr, $level:expr, $message:expr) => {
+    $crate::log_located($channel, $level, file!(), line!(), $message)
+  };
+}
+
+/// Log to `Channel::Critical` at `level`, capturing the call site.
+#[macro_export]
+macro_rules! critical {
+  ($level:expr, $message:expr) => {
+    $crate::log!($crate::Channel::Critical, $level, $message)
+  };
+}
+
+/// Log to `Channel::Error` at `level`, capturing the call site.
+#[macro_export]
+macro_rules! error {
+  ($level:expr, $message:expr) => {
+    $crate::log!($crate::Channel::Error, $level, $message)
+  };
+}
+
+/// Log to `Channel::Warning` at `level`, capturing the call site.
+#[macro_export]
+macro_rules! warning {
+  ($level:expr, $message:expr) => {
+    $crate::log!($crate::Channel::Warning, $level, $message)
+  };
+}
+
+/// Log to `Channel::Notice` at `level`, capturing the call site.
+#[macro_export]
+macro_rules! notice {
+  ($level:expr, $message:expr) => {
+    $crate::log!($crate::Channel::Notice, $level, $message)
+  };
+}
+
+/// Log to `Channel::Info` at `level`, capturing the call site.
+#[macro_export]
+macro_rules! info {
+  ($level:expr, $message:expr) => {
+    $crate::log!($crate::Channel::Info, $level, $message)
+  };
+}
+
+/// Log to `Channel::Debug` at `level`, capturing the call site.
+#[macro_export]
+macro_rules! debug {
+  ($level:expr, $message:expr) => {
+    $crate::log!($crate::Channel::Debug, $level, $message)
+  };
+}
+
+/// Log to `Channel::Trace` at `level`, capturing the call site.
+#[macro_export]
+macro_rules! trace {
+  ($level:expr, $message:expr) => {
+    $crate::log!($crate::Channel::Trace, $level, $message)
+  };
+}
+
+
+/// A [`log::Log`] implementation that routes records from the `log` facade through `tiny_logger`'s
+/// channels, colors, and streams. Only available with the `log-compat` feature.
+#[cfg(feature = "log-compat")]
+pub struct TinyLogger;
+
+#[cfg(feature = "log-compat")]
+impl TinyLogger {
+  /// Convert a `log::Level` into the corresponding `Channel`.
+  fn channel_for_level(level: log::Level) -> Channel {
+    match level {
+      log::Level::Error => Channel::Error,
+      log::Level::Warn  => Channel::Warning,
+      log::Level::Info  => Channel::Info,
+      log::Level::Debug => Channel::Debug,
+      log::Level::Trace => Channel::Trace,
+    }
+  }
+
+  /// Translate a `LogLevel` verbosity into a `log::LevelFilter`.
+  fn level_filter_for(verbosity: LogLevel) -> log::LevelFilter {
+    match verbosity {
+      v if v < 0 => log::LevelFilter::Off,
+      0          => log::LevelFilter::Error,
+      1          => log::LevelFilter::Warn,
+      2          => log::LevelFilter::Info,
+      3          => log::LevelFilter::Debug,
+      _          => log::LevelFilter::Trace,
+    }
+  }
+
+  /// Translate the global `VERBOSITY` into a `log::LevelFilter`.
+  fn level_filter() -> log::LevelFilter {
+    Self::level_filter_for(get_verbosity())
+  }
+
+  /// Recompute the `log` crate's static max-level gate from the current global verbosity and
+  /// every per-channel override, so a per-channel increase (e.g.
+  /// `Channel::Debug.set_verbosity(10)`) isn't silently blocked by a stale, more restrictive
+  /// global filter. `set_verbosity` and `Channel::set_verbosity` call this automatically; it is
+  /// a no-op (beyond the redundant `log::set_max_level` call) if `try_init`/`init` was never
+  /// called.
+  fn refresh_max_level() {
+    let mut filter = Self::level_filter();
+    for channel in ALL_CHANNELS {
+      let channel_filter = Self::level_filter_for(channel.get_verbosity());
+      if channel_filter > filter {
+        filter = channel_filter;
+      }
     }
-    // Note: If there is no stream for the given channel, we just don't emit the message.
+    log::set_max_level(filter);
   }
 }
 
+#[cfg(feature = "log-compat")]
+impl log::Log for TinyLogger {
+  fn enabled(&self, metadata: &log::Metadata) -> bool {
+    let channel = Self::channel_for_level(metadata.level());
+    metadata.level() <= Self::level_filter_for(channel.get_verbosity())
+  }
+
+  fn log(&self, record: &log::Record) {
+    if self.enabled(record.metadata()) {
+      let channel = Self::channel_for_level(record.level());
+      self::log(channel, 0, &record.args().to_string());
+    }
+  }
+
+  fn flush(&self) {}
+}
+
+/// Install `TinyLogger` as the backend for the `log` facade, panicking if a logger is already
+/// installed. Only available with the `log-compat` feature.
+#[cfg(feature = "log-compat")]
+pub fn init() {
+  try_init().expect("tiny_logger::init should not be called after the logger is already set");
+}
+
+/// Install `TinyLogger` as the backend for the `log` facade. Only available with the `log-compat`
+/// feature.
+#[cfg(feature = "log-compat")]
+pub fn try_init() -> Result<(), log::SetLoggerError> {
+  log::set_boxed_logger(Box::new(TinyLogger))?;
+  TinyLogger::refresh_max_level();
+  Ok(())
+}
+
 
 #[cfg(test)]
 mod tests {
   use super::*;
   use yansi::Color;
 
+  // Every test below reads or mutates the crate's global state (colors, verbosity, streams,
+  // async/formatter config, ...), so they must not run concurrently with each other.
+  static TEST_MUTEX: Mutex<()> = Mutex::new(());
+
+  fn lock_test() -> std::sync::MutexGuard<'static, ()> {
+    TEST_MUTEX.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+
   #[test]
   fn test_channel_colors_initialization() {
+    let _guard = lock_test();
     // Verify initial colors
     let colors = CHANNEL_COLORS.read().unwrap();
     assert_eq!(*colors.get(&Channel::Critical).unwrap(), Color::Red);
@@ -270,6 +1053,7 @@ mod tests {
 
   #[test]
   fn test_set_color() {
+    let _guard = lock_test();
     // Change the color of the Info channel
     Channel::Info.set_color(Color::Magenta);
     {
@@ -282,12 +1066,14 @@ mod tests {
 
   #[test]
   fn test_get_color() {
+    let _guard = lock_test();
     let critical_color = Channel::Critical.get_color();
     assert_eq!(critical_color, Color::Red);
   }
 
   #[test]
   fn test_painted_name() {
+    let _guard = lock_test();
     let painted_name = Channel::Warning.painted_name();
     let expected = "Warning".to_string().paint(Color::Yellow).to_string();
     assert_eq!(painted_name.to_string(), expected);
@@ -295,6 +1081,7 @@ mod tests {
 
   #[test]
   fn test_set_and_get_verbosity() {
+    let _guard = lock_test();
     // Set verbosity and check if it was set correctly
     set_verbosity(3);
     {
@@ -307,6 +1094,7 @@ mod tests {
 
   #[test]
   fn test_logging() {
+    let _guard = lock_test();
     // Create a buffer to capture log output wrapped in Arc<Mutex<dyn Write>>
     let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
 
@@ -352,4 +1140,313 @@ mod tests {
     // Reset verbosity to default for subsequent tests.
     set_verbosity(0)
   }
+
+  #[test]
+  fn test_channel_verbosity_overrides_global() {
+    let _guard = lock_test();
+    set_verbosity(0);
+    assert_eq!(Channel::Trace.get_verbosity(), 0); // Falls back to global.
+
+    Channel::Trace.set_verbosity(5);
+    assert_eq!(Channel::Trace.get_verbosity(), 5);
+    assert_eq!(Channel::Debug.get_verbosity(), 0); // Unaffected.
+
+    Channel::Trace.set_verbosity(LogLevel::MIN);
+    assert!(Channel::Trace.get_verbosity() < 0);
+
+    Channel::Trace.set_verbosity(0); // Reset to 0 for later tests (an explicit override, equal to the default global).
+  }
+
+  #[test]
+  fn test_set_from_env_parses_directives() {
+    let _guard = lock_test();
+    std::env::set_var(
+      "TINY_LOGGER_TEST_DIRECTIVE",
+      "notice,trace=2,debug=off,unknown_channel=5,garbage"
+    );
+    set_from_env("TINY_LOGGER_TEST_DIRECTIVE");
+
+    assert_eq!(get_verbosity(), Channel::Notice.ordinal());
+    assert_eq!(Channel::Trace.get_verbosity(), 2);
+    assert!(Channel::Debug.get_verbosity() < 0);
+
+    // Reset for subsequent tests.
+    set_verbosity(0);
+    Channel::Trace.set_verbosity(0);
+    Channel::Debug.set_verbosity(0);
+    std::env::remove_var("TINY_LOGGER_TEST_DIRECTIVE");
+  }
+
+  #[test]
+  fn test_timestamp_prefix() {
+    let _guard = lock_test();
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    Channel::Info.set_stream(Arc::clone(&(buffer.clone() as Arc<Mutex<dyn Write + Send>>)));
+    set_verbosity(1);
+    disable_color();
+
+    assert_eq!(get_timestamp(), TimestampMode::Off); // Default.
+
+    set_timestamp(TimestampMode::Utc);
+    set_timestamp_format("%Y-%m-%d");
+    log(Channel::Info, 0, "Timestamped message.");
+
+    let logged_string = String::from_utf8_lossy(&buffer.lock().unwrap()).to_string();
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    assert!(logged_string.contains(&today));
+    assert!(logged_string.contains("Timestamped message."));
+
+    // Reset for subsequent tests.
+    set_timestamp(TimestampMode::Off);
+    set_verbosity(0);
+  }
+
+  #[test]
+  fn test_source_location_suffix() {
+    let _guard = lock_test();
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    Channel::Debug.set_stream(Arc::clone(&(buffer.clone() as Arc<Mutex<dyn Write + Send>>)));
+    set_verbosity(1);
+    disable_color();
+
+    show_source_location(&[Channel::Debug]);
+    debug!(0, "Variable values are correct.");
+
+    let logged_string = String::from_utf8_lossy(&buffer.lock().unwrap()).to_string();
+    assert!(logged_string.contains("Variable values are correct."));
+    assert!(logged_string.contains("lib.rs:"));
+
+    // Reset for subsequent tests.
+    show_source_location(&[]);
+    set_verbosity(0);
+  }
+
+  #[test]
+  fn test_async_logging_round_trip() {
+    let _guard = lock_test();
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    Channel::Info.set_stream(Arc::clone(&(buffer.clone() as Arc<Mutex<dyn Write + Send>>)));
+    set_verbosity(1);
+    disable_color();
+
+    enable_async(8, AsyncPolicy::Block);
+    assert!(is_async_enabled());
+
+    log(Channel::Info, 0, "Async message.");
+    flush();
+
+    let logged_string = String::from_utf8_lossy(&buffer.lock().unwrap()).to_string();
+    assert!(logged_string.contains("Async message."));
+
+    disable_async();
+    assert!(!is_async_enabled());
+    set_verbosity(0);
+  }
+
+  #[test]
+  fn test_async_drop_policy_counts_full_queue() {
+    let _guard = lock_test();
+    set_verbosity(0);
+
+    // A rendezvous (zero-capacity) channel with no receiver ever polling it: every send is
+    // guaranteed to observe a full channel, so this deterministically exercises the Drop arm
+    // of `dispatch_async` without depending on how fast a real worker thread drains it.
+    let (sender, _receiver) = std::sync::mpsc::sync_channel::<AsyncMessage>(0);
+    *ASYNC_POLICY.write().unwrap() = AsyncPolicy::Drop;
+
+    let before = dropped_count();
+    for i in 0..5 {
+      dispatch_async(&sender, Channel::Critical, format!("Message {i}"));
+    }
+
+    assert_eq!(dropped_count(), before + 5);
+  }
+
+  #[test]
+  fn test_async_worker_recovers_from_write_panic() {
+    let _guard = lock_test();
+
+    // A `Write` impl that panics on its first call and behaves normally afterward, simulating a
+    // transient failure like a broken pipe.
+    struct PanicOnceWriter {
+      calls: std::sync::atomic::AtomicUsize,
+      inner: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl Write for PanicOnceWriter {
+      fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+          panic!("simulated write failure");
+        }
+        self.inner.lock().unwrap().write(buf)
+      }
+
+      fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+      }
+    }
+
+    let inner: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    let writer = PanicOnceWriter { calls: std::sync::atomic::AtomicUsize::new(0), inner: inner.clone() };
+    Channel::Info.set_stream(Arc::new(Mutex::new(writer)) as Arc<Mutex<dyn Write + Send>>);
+    set_verbosity(1);
+    disable_color();
+
+    let before = worker_panic_count();
+    enable_async(8, AsyncPolicy::Block);
+
+    log(Channel::Info, 0, "Lost to the simulated write panic.");
+    log(Channel::Info, 0, "Should still make it through.");
+    flush();
+
+    assert_eq!(worker_panic_count(), before + 1);
+    let logged_string = String::from_utf8_lossy(&inner.lock().unwrap()).to_string();
+    assert!(logged_string.contains("Should still make it through."));
+
+    disable_async();
+    set_verbosity(0);
+
+    // Restore the default stream for subsequent tests.
+    Channel::Info.set_stream(Arc::new(Mutex::new(std::io::stdout())) as Arc<Mutex<dyn Write + Send>>);
+  }
+
+  #[test]
+  fn test_set_stream_with_tty_suppresses_color() {
+    let _guard = lock_test();
+    assert!(Channel::Notice.is_terminal()); // Default.
+
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    Channel::Notice.set_stream_with_tty(Arc::clone(&(buffer.clone() as Arc<Mutex<dyn Write + Send>>)), false);
+    assert!(!Channel::Notice.is_terminal());
+
+    enable_color();
+    let painted = Channel::Notice.painted_name();
+    assert_eq!(painted.to_string(), "Notice"); // Unstyled even with color enabled.
+
+    // Restore default stream/hint for subsequent tests.
+    let stdout_stream: Arc<Mutex<dyn Write + Send>> = Arc::new(Mutex::new(std::io::stdout()));
+    Channel::Notice.set_stream_with_tty(stdout_stream, true);
+  }
+
+  #[test]
+  fn test_ready_made_formatters() {
+    let _guard = lock_test();
+    let record = FormatRecord {
+      channel:   Channel::Info,
+      color:     Channel::Info.get_color(),
+      level:     2,
+      message:   "hello \"world\"",
+      timestamp: None,
+      location:  None,
+    };
+
+    assert_eq!(plain(&record), "Info: hello \"world\"");
+    assert_eq!(with_level_number(&record), "[2] Info: hello \"world\"");
+    assert_eq!(json_line(&record), r#"{"channel":"Info","level":2,"msg":"hello \"world\""}"#);
+  }
+
+  #[test]
+  fn test_json_line_escapes_all_control_characters() {
+    let _guard = lock_test();
+    let record = FormatRecord {
+      channel:   Channel::Info,
+      color:     Channel::Info.get_color(),
+      level:     0,
+      message:   "bell\u{7}vtab\u{b}unitsep\u{1f}",
+      timestamp: None,
+      location:  None,
+    };
+
+    let line = json_line(&record);
+    assert_eq!(line, r#"{"channel":"Info","level":0,"msg":"bell\u0007vtab\u000bunitsep\u001f"}"#);
+  }
+
+  #[test]
+  fn test_set_formatter_overrides_default_layout() {
+    let _guard = lock_test();
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    Channel::Info.set_stream(Arc::clone(&(buffer.clone() as Arc<Mutex<dyn Write + Send>>)));
+    set_verbosity(1);
+
+    set_formatter(Box::new(plain));
+    log(Channel::Info, 0, "Formatted message.");
+
+    let logged_string = String::from_utf8_lossy(&buffer.lock().unwrap()).to_string();
+    assert_eq!(logged_string.trim_end(), "Info: Formatted message.");
+
+    // Reset for subsequent tests.
+    clear_formatter();
+    set_verbosity(0);
+  }
+
+  #[cfg(feature = "log-compat")]
+  #[test]
+  fn test_log_compat_channel_mapping() {
+    let _guard = lock_test();
+    assert_eq!(TinyLogger::channel_for_level(log::Level::Error), Channel::Error);
+    assert_eq!(TinyLogger::channel_for_level(log::Level::Warn),  Channel::Warning);
+    assert_eq!(TinyLogger::channel_for_level(log::Level::Info),  Channel::Info);
+    assert_eq!(TinyLogger::channel_for_level(log::Level::Debug), Channel::Debug);
+    assert_eq!(TinyLogger::channel_for_level(log::Level::Trace), Channel::Trace);
+  }
+
+  #[cfg(feature = "log-compat")]
+  #[test]
+  fn test_enabled_respects_off_sentinel() {
+    let _guard = lock_test();
+    use log::Log;
+
+    set_verbosity(LogLevel::MIN);
+    let metadata = log::Metadata::builder().level(log::Level::Error).target("test").build();
+    assert!(!TinyLogger.enabled(&metadata));
+    set_verbosity(0);
+  }
+
+  #[cfg(feature = "log-compat")]
+  #[test]
+  fn test_try_init_routes_log_macros_through_channel() {
+    let _guard = lock_test();
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    Channel::Error.set_stream(Arc::clone(&(buffer.clone() as Arc<Mutex<dyn Write + Send>>)));
+    set_verbosity(0); // Error is enabled at the default verbosity.
+    disable_color();
+
+    // Ignore the error if some other test already installed the global logger first.
+    let _ = try_init();
+
+    log::error!("Routed through the log facade.");
+
+    let logged_string = String::from_utf8_lossy(&buffer.lock().unwrap()).to_string();
+    assert!(logged_string.contains("Routed through the log facade."));
+
+    set_verbosity(0);
+  }
+
+  #[cfg(feature = "log-compat")]
+  #[test]
+  fn test_per_channel_verbosity_refreshes_log_max_level() {
+    let _guard = lock_test();
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    Channel::Debug.set_stream(Arc::clone(&(buffer.clone() as Arc<Mutex<dyn Write + Send>>)));
+    set_verbosity(0); // Debug is off at the default verbosity.
+    disable_color();
+
+    // Ignore the error if some other test already installed the global logger first.
+    let _ = try_init();
+
+    // `log::debug!` must still be unreachable at this point.
+    log::debug!("Should not appear.");
+    assert!(buffer.lock().unwrap().is_empty());
+
+    // Raising only Debug's own verbosity must raise the log crate's static max-level gate too,
+    // not just TinyLogger::enabled()'s own check.
+    Channel::Debug.set_verbosity(10);
+    log::debug!("Routed through the log facade.");
+
+    let logged_string = String::from_utf8_lossy(&buffer.lock().unwrap()).to_string();
+    assert!(logged_string.contains("Routed through the log facade."));
+
+    Channel::Debug.set_verbosity(0);
+    set_verbosity(0);
+  }
 }